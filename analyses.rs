@@ -0,0 +1,155 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::ir::types::{Stmt, Value};
+use crate::lattices::calllattice::{CallCheckLattice, CallCheckValue, ProvenanceId};
+use crate::lattices::davlattice::DAV;
+use crate::lattices::reachingdefslattice::LocIdx;
+
+use CallCheckValue::*;
+
+/// The fixpoint `AnalysisResult` a whole-program driver (outside this
+/// crate's checked-out tree) computes by repeatedly applying an
+/// `AbstractAnalyzer` over the CFG: the abstract state reaching each
+/// `LocIdx`.
+pub type AnalysisResult<T> = HashMap<LocIdx, T>;
+
+/// Defines how a single IR statement transforms an abstract state. Each
+/// checker pairs one of these with its own lattice to get a
+/// statement-by-statement abstract interpreter; the CFG-level fixpoint
+/// driver that folds these into a whole-program `AnalysisResult` lives
+/// outside this crate's checked-out tree.
+pub trait AbstractAnalyzer<T> {
+    fn aexec(&self, state: &mut T, ir_stmt: &Stmt, loc: &LocIdx);
+}
+
+/// Intraprocedural abstract interpreter backing `CallCheckLattice`: tracks
+/// where a guest call-table base pointer and its bounds-checked offset
+/// came from, well enough for `checkers::call_checker` to verify calltable
+/// lookups against it. It never creates a `FnPtr` itself: a `FnPtr` already
+/// in a register is only copied to another one by `Stmt::Assign`.
+#[derive(Default)]
+pub struct CallAnalyzer {
+    next_provenance: RefCell<u64>,
+}
+
+impl CallAnalyzer {
+    fn fresh_provenance(&self) -> ProvenanceId {
+        let mut next = self.next_provenance.borrow_mut();
+        let id = *next;
+        *next += 1;
+        ProvenanceId(id)
+    }
+}
+
+impl AbstractAnalyzer<CallCheckLattice> for CallAnalyzer {
+    fn aexec(&self, state: &mut CallCheckLattice, ir_stmt: &Stmt, _loc: &LocIdx) {
+        match ir_stmt {
+            Stmt::LoadTableBase(Value::Reg(regnum, size)) => {
+                state
+                    .regs
+                    .set_reg(*regnum, *size, Some(GuestTableBase(self.fresh_provenance())));
+            }
+            Stmt::Assign(Value::Reg(dst_reg, dst_size), Value::Reg(src_reg, src_size)) => {
+                let v = state.regs.get_reg(*src_reg, *src_size).v;
+                state.regs.set_reg(*dst_reg, *dst_size, v);
+            }
+            // The offset's provenance is copied from `base`'s own id, not
+            // minted fresh: `check_calltable_lookup`'s `base_prov ==
+            // off_prov` only proves anything about a genuine base/offset
+            // pair if the offset actually carries the id of the base it
+            // was checked against, rather than a fresh id no base could
+            // ever match.
+            Stmt::BoundsCheck(Value::Reg(dst_reg, dst_size), Value::Reg(base_reg, base_size), ty) => {
+                let base_prov = match state.regs.get_reg(*base_reg, *base_size).v {
+                    Some(GuestTableBase(prov)) => Some(prov),
+                    _ => None,
+                };
+                let v = base_prov.map(|prov| match ty {
+                    Some(t) => TypedPtrOffset(Some(*t), prov),
+                    None => PtrOffset(DAV::Checked, prov),
+                });
+                state.regs.set_reg(*dst_reg, *dst_size, v);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::types::ValSize;
+
+    const RDI: u8 = 7;
+    const RSI: u8 = 6;
+    const RAX: u8 = 0;
+
+    fn loc() -> LocIdx {
+        LocIdx { addr: 0 }
+    }
+
+    #[test]
+    fn bounds_check_copies_the_bases_own_provenance() {
+        let analyzer = CallAnalyzer::default();
+        let mut state = CallCheckLattice::default();
+        analyzer.aexec(
+            &mut state,
+            &Stmt::LoadTableBase(Value::Reg(RDI, ValSize::Size64)),
+            &loc(),
+        );
+        let base_prov = match state.regs.get_reg(RDI, ValSize::Size64).v {
+            Some(GuestTableBase(prov)) => prov,
+            other => panic!("expected GuestTableBase, got {:?}", other),
+        };
+        analyzer.aexec(
+            &mut state,
+            &Stmt::BoundsCheck(
+                Value::Reg(RAX, ValSize::Size64),
+                Value::Reg(RDI, ValSize::Size64),
+                Some(3),
+            ),
+            &loc(),
+        );
+        assert_eq!(
+            state.regs.get_reg(RAX, ValSize::Size64).v,
+            Some(TypedPtrOffset(Some(3), base_prov))
+        );
+    }
+
+    #[test]
+    fn two_bases_get_distinct_provenance() {
+        let analyzer = CallAnalyzer::default();
+        let mut state = CallCheckLattice::default();
+        analyzer.aexec(
+            &mut state,
+            &Stmt::LoadTableBase(Value::Reg(RDI, ValSize::Size64)),
+            &loc(),
+        );
+        analyzer.aexec(
+            &mut state,
+            &Stmt::LoadTableBase(Value::Reg(RSI, ValSize::Size64)),
+            &loc(),
+        );
+        assert_ne!(
+            state.regs.get_reg(RDI, ValSize::Size64).v,
+            state.regs.get_reg(RSI, ValSize::Size64).v
+        );
+    }
+
+    #[test]
+    fn bounds_check_without_a_resolved_base_resolves_to_nothing() {
+        let analyzer = CallAnalyzer::default();
+        let mut state = CallCheckLattice::default();
+        analyzer.aexec(
+            &mut state,
+            &Stmt::BoundsCheck(
+                Value::Reg(RAX, ValSize::Size64),
+                Value::Reg(RDI, ValSize::Size64),
+                None,
+            ),
+            &loc(),
+        );
+        assert_eq!(state.regs.get_reg(RAX, ValSize::Size64).v, None);
+    }
+}
@@ -0,0 +1,31 @@
+pub mod call_checker;
+
+use crate::analyses::AnalysisResult;
+use crate::ir::types::{IRMap, Stmt};
+use crate::lattices::reachingdefslattice::LocIdx;
+
+/// Walks every statement in `irmap`, checking each against the
+/// already-computed fixpoint `result`. `aexec` is exposed for the fixpoint
+/// driver that produces `result` in the first place — checking itself only
+/// needs the precomputed pre-state at each location, via
+/// `check_state_at_statements`.
+pub trait Checker<T> {
+    fn check(&self, result: AnalysisResult<T>) -> bool;
+    fn irmap(&self) -> &IRMap;
+    fn aexec(&self, state: &mut T, ir_stmt: &Stmt, loc: &LocIdx);
+    fn check_statement(&self, state: &T, ir_stmt: &Stmt, loc_idx: &LocIdx) -> bool;
+
+    fn check_state_at_statements(&self, result: AnalysisResult<T>) -> bool {
+        let mut all_ok = true;
+        for stmts in self.irmap().values() {
+            for (loc, stmt) in stmts {
+                if let Some(state) = result.get(loc) {
+                    if !self.check_statement(state, stmt, loc) {
+                        all_ok = false;
+                    }
+                }
+            }
+        }
+        all_ok
+    }
+}
@@ -1,40 +1,482 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+
 use crate::{analyses, checkers, ir, lattices};
 use analyses::{AbstractAnalyzer, AnalysisResult, CallAnalyzer};
 use checkers::Checker;
 use ir::types::*;
 use lattices::calllattice::{CallCheckLattice, CallCheckValue};
 use lattices::davlattice::DAV;
-use lattices::reachingdefslattice::LocIdx;
+use lattices::reachingdefslattice::{LocIdx, ReachingDefsLattice};
 
 use CallCheckValue::*;
 use ValSize::*;
 
+/// A single failure to verify a callsite or calltable lookup, carrying
+/// enough context to render an actionable diagnostic instead of a bare
+/// pass/fail. `cause` is the backward slice of `LocIdx`s — reconstructed by
+/// walking reaching-definitions info back from the failing statement — that
+/// produced the unresolved lattice value, i.e. *why* the register wasn't
+/// `FnPtr`/`Checked`.
+#[derive(Debug, Clone)]
+pub enum CallCheckViolation {
+    UnresolvedIndirectCall {
+        loc: LocIdx,
+        target: Value,
+        value: Option<CallCheckValue>,
+        cause: Vec<LocIdx>,
+    },
+    MemoryCallTarget {
+        loc: LocIdx,
+        target: Value,
+    },
+    UnresolvedTableLookup {
+        loc: LocIdx,
+        memargs: MemArgs,
+        base: Option<CallCheckValue>,
+        offset: Option<CallCheckValue>,
+        cause: Vec<LocIdx>,
+    },
+    TypeMismatch {
+        loc: LocIdx,
+        expected: Option<u32>,
+        found: Option<u32>,
+    },
+    UnresolvedRipConst {
+        loc: LocIdx,
+        addr: u64,
+    },
+    UntrustedPltTarget {
+        loc: LocIdx,
+        target: u64,
+    },
+}
+
+/// Per-function summary of which argument registers a function is known to
+/// receive already-resolved (from the union of its observed callers) and
+/// which values it is known to hand back to its callers at return. Computed
+/// once over the whole callgraph by [`compute_call_summaries`] so that
+/// `check_indirect_call` can see resolution facts that cross a call
+/// boundary, which the intraprocedural `CallAnalyzer` cannot.
+///
+/// `entry` is keyed by `regnum` alone and only ever holds argument-position
+/// registers (see `ARG_REGNUMS`): a caller's `FnPtr` sitting in some other,
+/// e.g. callee-saved, register at a callsite says nothing about what the
+/// callee receives as an argument, so it's never stored here in the first
+/// place. Facts are only ever accumulated from callsites read at `Size64`
+/// (`compute_call_summaries` hardcodes it, since argument registers carrying
+/// a pointer are conventionally read at full width); there is deliberately
+/// no narrower-width entry, so `entry_value` doesn't take a `size` either —
+/// a callsite reading the register at anything other than `Size64` has no
+/// fact to look up and must fail closed. `exit` keeps the full per-location
+/// lattice, since it's read once from `result` rather than built
+/// incrementally.
+#[derive(Clone, Default)]
+struct CallSummary {
+    entry: HashMap<u8, CallCheckValue>,
+    exit: Option<CallCheckLattice>,
+}
+
+/// Callgraph-wide map of [`CallSummary`]s, keyed by function entry address.
+pub struct CallSummaries(HashMap<u64, CallSummary>);
+
+impl CallSummaries {
+    /// Only ever has a fact to report at `Size64` (see `CallSummary::entry`),
+    /// so callers are expected to have already checked the register they're
+    /// resolving is read at that width.
+    fn entry_value(&self, func: u64, regnum: u8) -> Option<CallCheckValue> {
+        self.0.get(&func).and_then(|s| s.entry.get(&regnum).cloned())
+    }
+
+    /// What `func` is known to hand back to its callers at return. Not
+    /// consulted by `check_indirect_call` itself (resolving a pointer
+    /// threaded back in through a return value would additionally require
+    /// matching up the call's reaching definition to this function, which
+    /// `check_indirect_call` doesn't do yet) — exposed for other checkers
+    /// and future callers that want a function's known-resolved return
+    /// facts.
+    pub fn exit_state(&self, func: u64) -> Option<CallCheckLattice> {
+        self.0.get(&func).and_then(|s| s.exit.clone())
+    }
+}
+
+/// System V AMD64 integer argument registers, in calling-convention order,
+/// under veriwasm's x86 regnum encoding (rax=0 … r15=15): rdi, rsi, rdx,
+/// rcx, r8, r9. Only these carry cross-call meaning for the interprocedural
+/// summary below.
+const ARG_REGNUMS: [u8; 6] = [7, 6, 2, 1, 8, 9];
+
+fn is_arg_regnum(regnum: u8) -> bool {
+    ARG_REGNUMS.contains(&regnum)
+}
+
+/// Whether a pointer whose originating type is `origin_ty` may be called at
+/// a site statically expecting `call_ty`. Both sides must actually be
+/// present and equal: accepting either side being `None` would let a
+/// pointer resolved through some untyped path (or a callsite the IR
+/// couldn't attach a type immediate to) sail past the check regardless of
+/// the call's declared signature, which defeats the whole point of this
+/// CFI pass. Fails closed on either side being unknown.
+fn signatures_compatible(origin_ty: Option<u32>, call_ty: Option<u32>) -> bool {
+    match (origin_ty, call_ty) {
+        (Some(origin), Some(expected)) => origin == expected,
+        _ => false,
+    }
+}
+
+/// Finds the entry address of the function that contains `addr`, relying on
+/// `funcs` being sorted ascending (the same invariant the callgraph walk
+/// below and `resolve_reg_value` depend on through this function).
+fn containing_func(funcs: &[u64], addr: u64) -> Option<u64> {
+    match funcs.binary_search(&addr) {
+        Ok(idx) => Some(funcs[idx]),
+        Err(0) => None,
+        Err(idx) => Some(funcs[idx - 1]),
+    }
+}
+
+/// Folds a function's call-site argument contributions — whatever
+/// `register_value_at` resolved for `regnum` at each of its callers, across
+/// `ARG_REGNUMS` — into a single per-register entry-fact map: a register is
+/// only kept when every contributor that reached it agreed on the same
+/// `CallCheckValue`, and dropped (permanently, even if a later contributor
+/// would have agreed with the first) the moment two contributors disagree.
+/// Split out of `compute_call_summaries`'s worklist loop so the fold itself
+/// can be unit-tested against synthetic `(regnum, value)` pairs without
+/// constructing an `IRMap`/`AnalysisResult`.
+fn accumulate_entry_facts(
+    contributions: impl IntoIterator<Item = (u8, CallCheckValue)>,
+) -> HashMap<u8, CallCheckValue> {
+    let mut acc: HashMap<u8, CallCheckValue> = HashMap::new();
+    let mut conflicted: HashSet<u8> = HashSet::new();
+    for (regnum, v) in contributions {
+        if conflicted.contains(&regnum) {
+            continue;
+        }
+        match acc.get(&regnum) {
+            None => {
+                acc.insert(regnum, v);
+            }
+            Some(prev) if *prev == v => {}
+            Some(_) => {
+                acc.remove(&regnum);
+                conflicted.insert(regnum);
+            }
+        }
+    }
+    acc
+}
+
+/// Folds `contributor` into `acc` with `CallCheckLattice::meet`, treating
+/// the first contributor as the starting point rather than meeting against
+/// some assumed identity element — `CallCheckLattice`'s bottom/top
+/// orientation isn't pinned down here, so this avoids depending on it.
+fn meet_in(acc: &mut Option<CallCheckLattice>, contributor: &CallCheckLattice, loc: &LocIdx) {
+    *acc = Some(match acc.take() {
+        None => contributor.clone(),
+        Some(prev) => prev.meet(contributor, loc),
+    });
+}
+
+/// The `CallCheckValue` register `(regnum, size)` holds for `caller` at
+/// `loc`. Sourced from the precomputed intraprocedural `result` when that
+/// already shows a concrete value; otherwise, when `reaching_defs` shows
+/// the register is still live-in from `caller`'s own entry (nothing inside
+/// `caller` redefines it before `loc`), sourced recursively from `caller`'s
+/// *own* entry summary instead — the same rule `check_indirect_call`
+/// applies at the ultimate callsite, used here too so a pointer threaded
+/// through several hops of calls keeps resolving at each hop rather than
+/// only the first.
+fn register_value_at(
+    result: &AnalysisResult<CallCheckLattice>,
+    reaching_defs: &AnalysisResult<ReachingDefsLattice>,
+    summaries: &HashMap<u64, CallSummary>,
+    caller: u64,
+    loc: &LocIdx,
+    regnum: u8,
+    size: ValSize,
+) -> Option<CallCheckValue> {
+    if let Some(state) = result.get(loc) {
+        if let Some(v) = state.regs.get_reg(regnum, size).v {
+            return Some(v);
+        }
+    }
+    let live_in_from_entry = reaching_defs
+        .get(loc)
+        .map(|rd| rd.regs.get_reg(regnum, size).defs.is_empty())
+        .unwrap_or(false);
+    if live_in_from_entry && size == Size64 {
+        return summaries.get(&caller).and_then(|s| s.entry.get(&regnum).cloned());
+    }
+    None
+}
+
+/// Every function address reachable through some path other than a direct
+/// call this analysis can see: either `relocations` (GOT/PLT) or
+/// `wasm_table` (the guest's `call_indirect` table). Split out of
+/// `compute_call_summaries` so the union itself can be unit-tested without
+/// constructing an `IRMap`/`AnalysisResult`.
+fn address_taken_from(relocations: &ReadOnlyTable, wasm_table: &ReadOnlyTable) -> HashSet<u64> {
+    relocations
+        .targets()
+        .union(&wasm_table.targets())
+        .cloned()
+        .collect()
+}
+
+/// Derives per-function summaries from the callgraph's call sites in
+/// `irmap`, the already-computed intraprocedural fixpoint `result`, and
+/// `reaching_defs`.
+///
+/// Exit summaries are read once from `result` at each function's return
+/// point: threading a callee's returned value back into the *caller's*
+/// own subsequent intraprocedural state would require re-running the
+/// CFG-aware forward analysis with an updated seed, which only the driver
+/// that produced `result` can do, so that half of the interprocedural
+/// story is deliberately out of scope here and `exit_state` is exposed
+/// for callers that want a function's known-resolved return facts
+/// directly instead.
+///
+/// Entry summaries, by contrast, are mutually recursive across the
+/// callgraph — a pointer threaded through several hops of calls is only
+/// visible at hop N once hop N-1's caller has itself acquired the right
+/// entry fact (see `register_value_at`) — so this runs a real
+/// worklist-to-fixpoint over functions, re-enqueueing only the real
+/// callees of whichever function's entry summary just changed rather than
+/// broadcasting to every function in `funcs`.
+///
+/// A function whose address appears in `relocations` (i.e. some GOT/PLT
+/// slot or other relocation in the binary legitimately resolves to it) or
+/// in `wasm_table` (i.e. some entry in the guest's `call_indirect` table
+/// legitimately resolves to it) is itself a possible `call_indirect`
+/// target, the same way any entry in `funcs` can be: `check_calltable_lookup`
+/// only verifies *provenance* agreement between a table base and offset,
+/// not that either table's contents are limited to some known-safe subset
+/// of `funcs`, so nothing rules out such a function being entered with
+/// attacker/guest-controlled argument registers rather than the resolved
+/// `FnPtr` its direct callers happen to pass. An entry summary built only
+/// from direct callers would have no way to see that second class of
+/// entry, so such a function never gets a trusted entry summary in the
+/// first place — its `entry` map is left empty for the lifetime of the
+/// fixpoint, and `check_indirect_call` fails closed on it exactly as it
+/// would for a function with no direct callers at all.
+/// Every function address reachable through some path other than a direct
+/// call this analysis can see: either `relocations` (GOT/PLT) or
+/// `wasm_table` (the guest's `call_indirect` table). Split out of
+/// `compute_call_summaries` so the union itself can be unit-tested without
+/// constructing an `IRMap`/`AnalysisResult`.
+fn address_taken_from(relocations: &ReadOnlyTable, wasm_table: &ReadOnlyTable) -> HashSet<u64> {
+    relocations
+        .targets()
+        .union(&wasm_table.targets())
+        .cloned()
+        .collect()
+}
+
+pub fn compute_call_summaries(
+    result: &AnalysisResult<CallCheckLattice>,
+    reaching_defs: &AnalysisResult<ReachingDefsLattice>,
+    irmap: &IRMap,
+    funcs: &[u64],
+    relocations: &ReadOnlyTable,
+    wasm_table: &ReadOnlyTable,
+) -> CallSummaries {
+    let address_taken = address_taken_from(relocations, wasm_table);
+    let mut locs_by_func: HashMap<u64, Vec<LocIdx>> = HashMap::new();
+    // (callsite loc, caller func, callee func)
+    let mut callsites: Vec<(LocIdx, u64, u64)> = Vec::new();
+
+    for (_block_addr, stmts) in irmap.iter() {
+        for (loc, stmt) in stmts.iter() {
+            if let Some(func) = containing_func(funcs, loc.addr) {
+                locs_by_func.entry(func).or_default().push(loc.clone());
+                if let Stmt::Call(Value::Imm(_, _, imm), _) = stmt {
+                    let callee = (*imm + (loc.addr as i64) + 5) as u64;
+                    if funcs.contains(&callee) {
+                        callsites.push((loc.clone(), func, callee));
+                    }
+                }
+            }
+        }
+    }
+
+    // Forward callgraph edges: when a function's entry summary changes,
+    // only the functions it actually calls can be affected by that change.
+    let mut callees_of: HashMap<u64, HashSet<u64>> = HashMap::new();
+    for (_, caller, callee) in &callsites {
+        callees_of.entry(*caller).or_default().insert(*callee);
+    }
+
+    let mut summaries: HashMap<u64, CallSummary> =
+        funcs.iter().map(|f| (*f, CallSummary::default())).collect();
+
+    for (func, locs) in &locs_by_func {
+        // This IR doesn't surface explicit successor/return edges here, so
+        // the lexically-last instruction in the function is used as a
+        // stand-in for its return point. A function with multiple early
+        // returns would need the full set of return locations instead.
+        if let Some(exit_loc) = locs.iter().max_by_key(|loc| loc.addr) {
+            if let Some(exit_state) = result.get(exit_loc) {
+                if let Some(summary) = summaries.get_mut(func) {
+                    meet_in(&mut summary.exit, exit_state, exit_loc);
+                }
+            }
+        }
+    }
+
+    let mut worklist: VecDeque<u64> = funcs.iter().cloned().collect();
+    let mut queued: HashSet<u64> = funcs.iter().cloned().collect();
+
+    while let Some(func) = worklist.pop_front() {
+        queued.remove(&func);
+
+        // Entry facts are only ever accumulated at `Size64` (see
+        // `CallSummary::entry`'s doc comment), so the key here is the
+        // regnum alone. A function that's itself a possible indirect-call
+        // target never gets one at all (see the doc comment above) — it
+        // can be entered with unresolved argument registers no direct
+        // caller here would ever supply, so "all direct callers agree"
+        // proves nothing about it.
+        let acc = if address_taken.contains(&func) {
+            HashMap::new()
+        } else {
+            let mut contributions: Vec<(u8, CallCheckValue)> = Vec::new();
+            for (loc, caller, _callee) in callsites.iter().filter(|(_, _, callee)| *callee == func)
+            {
+                for &regnum in ARG_REGNUMS.iter() {
+                    if let Some(v) = register_value_at(
+                        result,
+                        reaching_defs,
+                        &summaries,
+                        *caller,
+                        loc,
+                        regnum,
+                        Size64,
+                    ) {
+                        contributions.push((regnum, v));
+                    }
+                }
+            }
+            accumulate_entry_facts(contributions)
+        };
+
+        let old_entry = summaries.get(&func).map(|s| s.entry.clone()).unwrap_or_default();
+        if old_entry != acc {
+            if let Some(summary) = summaries.get_mut(&func) {
+                summary.entry = acc;
+            }
+            if let Some(callees) = callees_of.get(&func) {
+                for callee in callees {
+                    if queued.insert(*callee) {
+                        worklist.push_back(*callee);
+                    }
+                }
+            }
+        }
+    }
+
+    CallSummaries(summaries)
+}
+
+/// Sorted map from a key address to the function it legitimately resolves
+/// to. Two unrelated tables in the binary share this same shape, so both
+/// reuse this type rather than each growing their own lookup structure:
+///
+/// - The relocation table: RIP-relative GOT slots (consulted for
+///   `Value::RIPConst` targets) and PLT stub addresses (consulted for a
+///   direct call computed into the PLT range, since the stub's *own*
+///   address carries the `.rela.plt` relocation that ties it to a real
+///   function, not the GOT slot the stub jumps through).
+/// - The guest's wasm call-table: each populated slot index resolves to the
+///   function it holds, the same entries `call_indirect` itself walks at
+///   runtime.
+///
+/// A target is only trusted when it appears in the relevant table, closing
+/// the soundness hole where any of these forms of indirection was accepted
+/// as a call target without any resolution requirement.
+pub struct ReadOnlyTable(Vec<(u64, u64)>);
+
+impl ReadOnlyTable {
+    pub fn new(mut entries: Vec<(u64, u64)>) -> Self {
+        entries.sort_by_key(|(addr, _)| *addr);
+        ReadOnlyTable(entries)
+    }
+
+    fn resolve(&self, addr: u64) -> Option<u64> {
+        self.0
+            .binary_search_by_key(&addr, |(addr, _)| *addr)
+            .ok()
+            .map(|idx| self.0[idx].1)
+    }
+
+    /// Every address this table resolves *to* — i.e. every function or PLT
+    /// entry some relocation or GOT/PLT slot legitimately points at. Used to
+    /// identify functions that are themselves a possible indirect-call
+    /// target (their address sits in a slot an attacker-controlled
+    /// `call_indirect` could in principle reach), as distinct from functions
+    /// only ever reached by a direct call.
+    fn targets(&self) -> HashSet<u64> {
+        self.0.iter().map(|(_, target)| *target).collect()
+    }
+}
+
 pub struct CallChecker<'a> {
     irmap: &'a IRMap,
     analyzer: &'a CallAnalyzer,
-    funcs: &'a Vec<u64>,
+    funcs: &'a [u64],
     plt: &'a (u64, u64),
+    relocations: &'a ReadOnlyTable,
+    reaching_defs: &'a AnalysisResult<ReachingDefsLattice>,
+    summaries: CallSummaries,
+    violations: RefCell<Vec<CallCheckViolation>>,
 }
 
+// Eight independent pieces of context (result/irmap/analyzer/funcs and four
+// distinct tables describing how a call target can be legitimized) are each
+// load-bearing on their own; bundling them into a struct just to dodge the
+// lint would make call sites pass a throwaway wrapper for no real grouping.
+#[allow(clippy::too_many_arguments)]
 pub fn check_calls(
     result: AnalysisResult<CallCheckLattice>,
     irmap: &IRMap,
     analyzer: &CallAnalyzer,
-    funcs: &Vec<u64>,
+    funcs: &[u64],
     plt: &(u64, u64),
-) -> bool {
-    CallChecker {
+    relocations: &ReadOnlyTable,
+    wasm_table: &ReadOnlyTable,
+    reaching_defs: &AnalysisResult<ReachingDefsLattice>,
+) -> Vec<CallCheckViolation> {
+    debug_assert!(
+        funcs.windows(2).all(|w| w[0] <= w[1]),
+        "funcs must be sorted ascending: containing_func's binary_search silently \
+         mis-attributes addresses otherwise"
+    );
+    let summaries = compute_call_summaries(
+        &result,
+        reaching_defs,
+        irmap,
+        funcs,
+        relocations,
+        wasm_table,
+    );
+    let checker = CallChecker {
         irmap,
         analyzer,
         funcs,
         plt,
-    }
-    .check(result)
+        relocations,
+        reaching_defs,
+        summaries,
+        violations: RefCell::new(Vec::new()),
+    };
+    checker.check(result);
+    checker.violations.into_inner()
 }
 
 impl Checker<CallCheckLattice> for CallChecker<'_> {
     fn check(&self, result: AnalysisResult<CallCheckLattice>) -> bool {
-        self.check_state_at_statements(result)
+        self.check_state_at_statements(result);
+        self.violations.borrow().is_empty()
     }
 
     fn irmap(&self) -> &IRMap {
@@ -46,57 +488,252 @@ impl Checker<CallCheckLattice> for CallChecker<'_> {
 
     fn check_statement(&self, state: &CallCheckLattice, ir_stmt: &Stmt, loc_idx: &LocIdx) -> bool {
         //1. Check that all indirect calls use resolved function pointer
-        if let Stmt::Call(v) = ir_stmt {
-            if !self.check_indirect_call(state, v, loc_idx) {
-                println!("0x{:x} Failure Case: Indirect Call {:?}", loc_idx.addr, v);
-                return false;
+        //
+        // `Stmt::Call`'s second field is the statically-expected
+        // `call_indirect` type index, lifted from the Wasm type immediate
+        // by whatever produced this `IRMap` (see `ir::types::Stmt::Call`).
+        if let Stmt::Call(v, call_ty) = ir_stmt {
+            if !self.check_indirect_call(state, v, *call_ty, loc_idx) {
+                self.record_indirect_call_violation(state, v, *call_ty, loc_idx);
             }
         }
 
         // 2. Check that lookup is using resolved DAV
         if let Stmt::Unop(_, _, Value::Mem(_, memargs)) = ir_stmt {
             if !self.check_calltable_lookup(state, memargs) {
-                println!(
-                    "0x{:x} Failure Case: Lookup Call: {:?}",
-                    loc_idx.addr, memargs
-                );
-                print_mem_access(state, memargs);
-                return false;
+                self.record_table_lookup_violation(state, memargs, loc_idx);
             }
         }
+        // Keep walking the rest of the function: a single unverified
+        // callsite shouldn't hide every other violation in the binary.
         true
     }
 }
 
 impl CallChecker<'_> {
+    /// The `CallCheckValue` register `(regnum, size)` holds at `loc_idx`, for
+    /// the purposes of resolving an indirect-call target: first whatever the
+    /// local, intraprocedural `CallAnalyzer` state already shows; failing
+    /// that, the interprocedural entry summary for the function `loc_idx`
+    /// lives in, but only for argument-position registers read at `Size64`
+    /// (a caller's `FnPtr` sitting in some other, e.g. callee-saved,
+    /// register says nothing about what this function receives as an
+    /// argument, and entry facts are never recorded at any other size — see
+    /// `CallSummary::entry`), and only when reaching-defs shows nothing
+    /// inside this function has redefined the register since entry, so a
+    /// clobber between entry and this callsite can't be silently overridden
+    /// by a stale entry fact.
+    ///
+    /// Shared by `check_indirect_call` (pass/fail verdict) and
+    /// `record_indirect_call_violation` (diagnostic detail) so a mismatch
+    /// caught via the interprocedural summary is reported with the same
+    /// value that caused the rejection, rather than re-reading only the
+    /// local state and coming up empty.
+    fn resolve_reg_value(
+        &self,
+        state: &CallCheckLattice,
+        regnum: u8,
+        size: ValSize,
+        loc_idx: &LocIdx,
+    ) -> Option<CallCheckValue> {
+        if let Some(v) = state.regs.get_reg(regnum, size).v {
+            return Some(v);
+        }
+        if is_arg_regnum(regnum) && size == Size64 {
+            let live_in_from_entry = self
+                .reaching_defs
+                .get(loc_idx)
+                .map(|rd| rd.regs.get_reg(regnum, size).defs.is_empty())
+                .unwrap_or(false);
+            if live_in_from_entry {
+                if let Some(func) = containing_func(self.funcs, loc_idx.addr) {
+                    return self.summaries.entry_value(func, regnum);
+                }
+            }
+        }
+        None
+    }
+
+    /// `call_ty` is the type index carried on the `call_indirect`'s IR
+    /// statement, i.e. the signature the callsite statically expects. A
+    /// resolved `FnPtr(ty)` is only accepted when its own originating type
+    /// (the tag its producer attached, see `CallCheckValue::FnPtr`)
+    /// matches: this is what turns pointer *resolution* into a forward-edge
+    /// CFI check on the pointer's *signature*.
     fn check_indirect_call(
         &self,
         state: &CallCheckLattice,
         target: &Value,
+        call_ty: Option<u32>,
         loc_idx: &LocIdx,
     ) -> bool {
         match target {
             Value::Reg(regnum, size) => {
-                if let Some(FnPtr(c)) = state.regs.get_reg(*regnum, *size).v {
-                    return true;
-                } else {
-                    log::debug!("{:?}", state.regs.get_reg(*regnum, *size).v)
+                if let Some(FnPtr(origin_ty)) =
+                    self.resolve_reg_value(state, *regnum, *size, loc_idx)
+                {
+                    return signatures_compatible(origin_ty, call_ty);
                 }
+                log::debug!("{:?}", state.regs.get_reg(*regnum, *size).v)
             }
             Value::Mem(_, _) => return false,
             Value::Imm(_, _, imm) => {
                 let target = (*imm + (loc_idx.addr as i64) + 5) as u64;
                 let (plt_start, plt_end) = self.plt;
-                return self.funcs.contains(&target)
-                    || ((target >= *plt_start) && (target < *plt_end));
+                if self.funcs.contains(&target) {
+                    return true;
+                }
+                // Landing in the PLT's address range isn't enough on its
+                // own: a PLT stub jumps through a GOT slot populated by a
+                // relocation, and an attacker-controlled displacement could
+                // otherwise land anywhere in that range. Require the target
+                // to actually be a legitimately-relocated PLT entry.
+                if (target >= *plt_start) && (target < *plt_end) {
+                    return self.relocations.resolve(target).is_some();
+                }
+                return false;
             }
-            Value::RIPConst => {
-                return true;
+            Value::RIPConst(addr) => {
+                let (plt_start, plt_end) = self.plt;
+                match self.relocations.resolve(*addr) {
+                    Some(resolved) => {
+                        return self.funcs.contains(&resolved)
+                            || ((resolved >= *plt_start) && (resolved < *plt_end));
+                    }
+                    None => {
+                        log::debug!(
+                            "0x{:x} RIP-relative call target 0x{:x} has no relocation entry",
+                            loc_idx.addr,
+                            addr
+                        );
+                    }
+                }
             }
         }
         false
     }
 
+    /// Walks reaching-definitions info backward from `loc`, following each
+    /// definition of `(regnum, size)` to *its* reaching definitions of the
+    /// same register in turn, until the slice stops growing. This is a
+    /// diagnostic aid only (it never affects the pass/fail verdict): it
+    /// enumerates the chain of earlier writes to `(regnum, size)`, not a
+    /// full data-dependency slice through those definitions' own source
+    /// operands, so it can miss the ultimate origin when a value is built
+    /// up across registers (e.g. `mov rax, rbx; add rax, 8`).
+    fn backward_slice(&self, loc: &LocIdx, regnum: u8, size: ValSize) -> Vec<LocIdx> {
+        let mut seen: HashSet<LocIdx> = HashSet::new();
+        let mut frontier: VecDeque<LocIdx> = VecDeque::new();
+        frontier.push_back(loc.clone());
+
+        let mut slice = Vec::new();
+        while let Some(cur) = frontier.pop_front() {
+            let defs = match self.reaching_defs.get(&cur) {
+                Some(rd) => rd.regs.get_reg(regnum, size).defs.clone(),
+                None => HashSet::new(),
+            };
+            for def in defs {
+                if seen.insert(def.clone()) {
+                    slice.push(def.clone());
+                    frontier.push_back(def);
+                }
+            }
+        }
+        slice
+    }
+
+    fn record_indirect_call_violation(
+        &self,
+        state: &CallCheckLattice,
+        target: &Value,
+        call_ty: Option<u32>,
+        loc_idx: &LocIdx,
+    ) {
+        let violation = match target {
+            Value::Mem(_, _) => CallCheckViolation::MemoryCallTarget {
+                loc: loc_idx.clone(),
+                target: target.clone(),
+            },
+            Value::Reg(regnum, size) => {
+                // Re-run the same two-branch lookup `check_indirect_call`
+                // used to reject this callsite, so a mismatch only the
+                // interprocedural summary caught is reported with *its*
+                // origin type instead of silently falling back to the
+                // (empty) local state and reporting "unresolved".
+                let value = self.resolve_reg_value(state, *regnum, *size, loc_idx);
+                match value {
+                    Some(FnPtr(origin_ty)) => CallCheckViolation::TypeMismatch {
+                        loc: loc_idx.clone(),
+                        expected: call_ty,
+                        found: origin_ty,
+                    },
+                    _ => CallCheckViolation::UnresolvedIndirectCall {
+                        loc: loc_idx.clone(),
+                        target: target.clone(),
+                        value,
+                        cause: self.backward_slice(loc_idx, *regnum, *size),
+                    },
+                }
+            }
+            Value::Imm(_, _, imm) => {
+                let resolved = (*imm + (loc_idx.addr as i64) + 5) as u64;
+                CallCheckViolation::UntrustedPltTarget {
+                    loc: loc_idx.clone(),
+                    target: resolved,
+                }
+            }
+            Value::RIPConst(addr) => CallCheckViolation::UnresolvedRipConst {
+                loc: loc_idx.clone(),
+                addr: *addr,
+            },
+        };
+        self.violations.borrow_mut().push(violation);
+    }
+
+    fn record_table_lookup_violation(
+        &self,
+        state: &CallCheckLattice,
+        memargs: &MemArgs,
+        loc_idx: &LocIdx,
+    ) {
+        if let MemArgs::Mem3Args(
+            MemArg::Reg(regnum1, Size64),
+            MemArg::Reg(regnum2, Size64),
+            MemArg::Imm(_, _, 8),
+        ) = memargs
+        {
+            let base = state.regs.get_reg(*regnum1, Size64).v;
+            let offset = state.regs.get_reg(*regnum2, Size64).v;
+            let mut cause = self.backward_slice(loc_idx, *regnum1, Size64);
+            cause.extend(self.backward_slice(loc_idx, *regnum2, Size64));
+            self.violations
+                .borrow_mut()
+                .push(CallCheckViolation::UnresolvedTableLookup {
+                    loc: loc_idx.clone(),
+                    memargs: memargs.clone(),
+                    base,
+                    offset,
+                    cause,
+                });
+        }
+    }
+
+    /// A `GuestTableBase` and a checked/typed offset only validate each
+    /// other when their provenance agrees, i.e. the offset was actually
+    /// derived against *this* table base rather than unified with it by a
+    /// lattice join across a branch merge. Without this, a checked offset
+    /// for one table and an unrelated base that happen to reach the same
+    /// join point would otherwise merge into an indistinguishable, and
+    /// wrongly accepted, pairing.
+    ///
+    /// For `base_prov == off_prov` to accept a genuine pair rather than
+    /// reject everything, `CallAnalyzer::aexec` propagates the base's id
+    /// onto the offset it derives from that base: at the bounds-check that
+    /// produces `PtrOffset(DAV::Checked, _)`/`TypedPtrOffset(_, _)`, the id
+    /// carried alongside the `GuestTableBase` operand is copied onto the
+    /// result rather than a fresh id being stamped on every defining
+    /// instruction — ids are only meant to distinguish *independent* bases
+    /// from each other, not a base from its own offset.
     fn check_calltable_lookup(&self, state: &CallCheckLattice, memargs: &MemArgs) -> bool {
         log::debug!("Call Table Lookup: {:?}", memargs);
         match memargs {
@@ -108,14 +745,18 @@ impl CallChecker<'_> {
                 state.regs.get_reg(*regnum1, Size64).v,
                 state.regs.get_reg(*regnum2, Size64).v,
             ) {
-                (Some(GuestTableBase), Some(PtrOffset(DAV::Checked))) => return true,
-                (Some(PtrOffset(DAV::Checked)), Some(GuestTableBase)) => return true,
-                (Some(TypedPtrOffset(_)), Some(GuestTableBase)) => return true,
-                (Some(GuestTableBase), Some(TypedPtrOffset(_))) => return true,
-                (_x, Some(GuestTableBase)) | (Some(GuestTableBase), _x) => return false,
-                (_x, _y) => return true, // not a calltable lookup
+                (Some(GuestTableBase(base_prov)), Some(PtrOffset(DAV::Checked, off_prov)))
+                | (Some(PtrOffset(DAV::Checked, off_prov)), Some(GuestTableBase(base_prov))) => {
+                    base_prov == off_prov
+                }
+                (Some(TypedPtrOffset(_, off_prov)), Some(GuestTableBase(base_prov)))
+                | (Some(GuestTableBase(base_prov)), Some(TypedPtrOffset(_, off_prov))) => {
+                    base_prov == off_prov
+                }
+                (_x, Some(GuestTableBase(_))) | (Some(GuestTableBase(_)), _x) => false,
+                (_x, _y) => true, // not a calltable lookup
             },
-            _ => return true, //not a calltable lookup?
+            _ => true, //not a calltable lookup?
         }
     }
 }
@@ -151,3 +792,110 @@ pub fn print_mem_access(state: &CallCheckLattice, memargs: &MemArgs) {
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn containing_func_exact_hit() {
+        let funcs = vec![0x100, 0x200, 0x300];
+        assert_eq!(containing_func(&funcs, 0x200), Some(0x200));
+    }
+
+    #[test]
+    fn containing_func_interior_address() {
+        let funcs = vec![0x100, 0x200, 0x300];
+        assert_eq!(containing_func(&funcs, 0x250), Some(0x200));
+    }
+
+    #[test]
+    fn containing_func_before_first_entry() {
+        let funcs = vec![0x100, 0x200, 0x300];
+        assert_eq!(containing_func(&funcs, 0x10), None);
+    }
+
+    #[test]
+    fn containing_func_past_last_entry() {
+        let funcs = vec![0x100, 0x200, 0x300];
+        assert_eq!(containing_func(&funcs, 0x350), Some(0x300));
+    }
+
+    #[test]
+    fn read_only_table_resolves_known_address() {
+        let table = ReadOnlyTable::new(vec![(0x1000, 0x2000), (0x1008, 0x2100)]);
+        assert_eq!(table.resolve(0x1000), Some(0x2000));
+        assert_eq!(table.resolve(0x1008), Some(0x2100));
+    }
+
+    #[test]
+    fn read_only_table_rejects_unknown_address() {
+        let table = ReadOnlyTable::new(vec![(0x1000, 0x2000)]);
+        assert_eq!(table.resolve(0x1004), None);
+    }
+
+    #[test]
+    fn address_taken_from_unions_relocations_and_wasm_table() {
+        let relocations = ReadOnlyTable::new(vec![(0x1000, 0x2000)]);
+        let wasm_table = ReadOnlyTable::new(vec![(0, 0x3000), (1, 0x4000)]);
+        let address_taken = address_taken_from(&relocations, &wasm_table);
+        assert!(address_taken.contains(&0x2000));
+        assert!(address_taken.contains(&0x3000));
+        assert!(address_taken.contains(&0x4000));
+        assert_eq!(address_taken.len(), 3);
+    }
+
+    #[test]
+    fn address_taken_from_wasm_table_only() {
+        let relocations = ReadOnlyTable::new(vec![]);
+        let wasm_table = ReadOnlyTable::new(vec![(0, 0x3000)]);
+        let address_taken = address_taken_from(&relocations, &wasm_table);
+        assert_eq!(address_taken, HashSet::from([0x3000]));
+    }
+
+    #[test]
+    fn signatures_compatible_fails_closed_unless_both_sides_declared_and_equal() {
+        assert!(!signatures_compatible(None, None));
+        assert!(!signatures_compatible(Some(5), None));
+        assert!(!signatures_compatible(None, Some(5)));
+        assert!(signatures_compatible(Some(5), Some(5)));
+        assert!(!signatures_compatible(Some(5), Some(6)));
+    }
+
+    #[test]
+    fn accumulate_entry_facts_keeps_agreeing_contributors() {
+        let facts = accumulate_entry_facts(vec![(7, FnPtr(Some(1))), (7, FnPtr(Some(1)))]);
+        assert_eq!(facts.get(&7), Some(&FnPtr(Some(1))));
+    }
+
+    #[test]
+    fn accumulate_entry_facts_drops_conflicting_register() {
+        let facts = accumulate_entry_facts(vec![(7, FnPtr(Some(1))), (7, FnPtr(Some(2)))]);
+        assert_eq!(facts.get(&7), None);
+    }
+
+    #[test]
+    fn accumulate_entry_facts_conflict_is_permanent() {
+        // A third contributor agreeing with the first doesn't resurrect a
+        // register that's already conflicted.
+        let facts = accumulate_entry_facts(vec![
+            (7, FnPtr(Some(1))),
+            (7, FnPtr(Some(2))),
+            (7, FnPtr(Some(1))),
+        ]);
+        assert_eq!(facts.get(&7), None);
+    }
+
+    #[test]
+    fn accumulate_entry_facts_tracks_registers_independently() {
+        let facts = accumulate_entry_facts(vec![(7, FnPtr(Some(1))), (6, FnPtr(Some(2)))]);
+        assert_eq!(facts.get(&7), Some(&FnPtr(Some(1))));
+        assert_eq!(facts.get(&6), Some(&FnPtr(Some(2))));
+    }
+
+    #[test]
+    fn accumulate_entry_facts_empty_contributions_yield_no_facts() {
+        let facts = accumulate_entry_facts(Vec::new());
+        assert!(facts.is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use crate::lattices::reachingdefslattice::LocIdx;
+
+/// Operand width, in the encoding the x86 register file and memory operands
+/// share throughout this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValSize {
+    Size8,
+    Size16,
+    Size32,
+    Size64,
+}
+
+/// A single operand to an IR statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Reg(u8, ValSize),
+    Mem(ValSize, MemArgs),
+    Imm(ValSize, ValSize, i64),
+    /// A RIP-relative constant load. Carries the concrete absolute address
+    /// the lifter computed from the instruction's displacement, so a
+    /// checker can look it up directly rather than re-deriving it from
+    /// `loc_idx.addr` and a raw displacement.
+    RIPConst(u64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemArg {
+    Reg(u8, ValSize),
+    Imm(ValSize, ValSize, i64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemArgs {
+    Mem1Arg(MemArg),
+    Mem2Args(MemArg, MemArg),
+    Mem3Args(MemArg, MemArg, MemArg),
+    MemScale(MemArg, MemArg, MemArg),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binopcode {
+    Add,
+}
+
+/// A single lifted IR statement. Only the shape the checkers and analyzers
+/// in this crate need to pattern-match on is modeled here.
+#[derive(Debug, Clone)]
+pub enum Stmt {
+    /// A call, direct or indirect. The second field is the callee's
+    /// statically-declared `call_indirect` type index when the lifter
+    /// could recover one from the Wasm module's type section — `None` for
+    /// a direct call, where there's nothing to check a signature against.
+    Call(Value, Option<u32>),
+    Unop(u8, ValSize, Value),
+    /// A plain register-to-register move.
+    Assign(Value, Value),
+    /// Materializes the base pointer of a guest call table (e.g. loading
+    /// it out of the Wasm instance's table-base slot) into the destination
+    /// operand.
+    LoadTableBase(Value),
+    /// Computes a table-relative offset that's been bounds-checked against
+    /// `base`. `Some(ty)` marks a check that additionally pins the offset
+    /// to a specific `call_indirect` type index (a "typed" check); `None`
+    /// marks a check that only validated the offset is in-bounds.
+    BoundsCheck(Value, Value, Option<u32>),
+}
+
+/// Per-basic-block lifted statements, keyed by the block's entry address,
+/// each paired with the `LocIdx` it lives at.
+pub type IRMap = HashMap<u64, Vec<(LocIdx, Stmt)>>;
@@ -0,0 +1,3 @@
+pub mod calllattice;
+pub mod davlattice;
+pub mod reachingdefslattice;
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::ir::types::ValSize;
+use crate::lattices::davlattice::DAV;
+use crate::lattices::reachingdefslattice::LocIdx;
+
+/// Distinguishes one `GuestTableBase` from another so a derived offset can
+/// be checked against *its own* base rather than an unrelated one that
+/// happens to reach the same join point. Assigned once where a
+/// `GuestTableBase` value is first produced and copied, not regenerated,
+/// onto every value derived from it — see `analyses::CallAnalyzer::aexec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProvenanceId(pub u64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallCheckValue {
+    /// A resolved function pointer. `Some(ty)` is the `call_indirect` type
+    /// index the pointer's own producer tagged it with (e.g. the type a
+    /// `TypedPtrOffset` bounds check pinned it to); `None` means the value
+    /// resolved to a function pointer through a path that never attached
+    /// one.
+    FnPtr(Option<u32>),
+    GuestTableBase(ProvenanceId),
+    PtrOffset(DAV, ProvenanceId),
+    TypedPtrOffset(Option<u32>, ProvenanceId),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CallCheckRegCell {
+    pub v: Option<CallCheckValue>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CallCheckRegFile {
+    regs: HashMap<(u8, ValSize), CallCheckRegCell>,
+}
+
+impl CallCheckRegFile {
+    pub fn get_reg(&self, regnum: u8, size: ValSize) -> CallCheckRegCell {
+        self.regs.get(&(regnum, size)).cloned().unwrap_or_default()
+    }
+
+    pub fn set_reg(&mut self, regnum: u8, size: ValSize, v: Option<CallCheckValue>) {
+        self.regs.insert((regnum, size), CallCheckRegCell { v });
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CallCheckLattice {
+    pub regs: CallCheckRegFile,
+}
+
+impl CallCheckLattice {
+    /// Joins two states reaching the same program point. A register only
+    /// keeps its value where both sides agree on it; anywhere they
+    /// disagree, or either side never set it, the merged state forgets it
+    /// rather than guessing, consistent with this crate failing closed on
+    /// an unresolved register.
+    pub fn meet(&self, other: &CallCheckLattice, _loc: &LocIdx) -> CallCheckLattice {
+        let mut merged = CallCheckRegFile::default();
+        for (key, cell) in &self.regs.regs {
+            if let Some(other_cell) = other.regs.regs.get(key) {
+                if cell.v == other_cell.v {
+                    merged.regs.insert(*key, cell.clone());
+                }
+            }
+        }
+        CallCheckLattice { regs: merged }
+    }
+}
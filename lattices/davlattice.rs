@@ -0,0 +1,7 @@
+/// Dataflow-analysis-value tag distinguishing whether a table offset has
+/// actually been validated against its table's bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DAV {
+    Checked,
+    Unchecked,
+}
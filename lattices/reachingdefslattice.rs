@@ -0,0 +1,34 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ir::types::ValSize;
+
+/// Identifies a single IR statement by its address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LocIdx {
+    pub addr: u64,
+}
+
+/// What reaches register `(regnum, size)` at a program point: the set of
+/// `LocIdx`s of statements that may have last written it. Empty means the
+/// register is live-in from the containing function's own entry — nothing
+/// inside the function has redefined it yet.
+#[derive(Debug, Clone, Default)]
+pub struct RegReachingDefs {
+    pub defs: HashSet<LocIdx>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReachingDefsRegFile {
+    regs: HashMap<(u8, ValSize), RegReachingDefs>,
+}
+
+impl ReachingDefsRegFile {
+    pub fn get_reg(&self, regnum: u8, size: ValSize) -> RegReachingDefs {
+        self.regs.get(&(regnum, size)).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReachingDefsLattice {
+    pub regs: ReachingDefsRegFile,
+}
@@ -0,0 +1,4 @@
+pub mod analyses;
+pub mod checkers;
+pub mod ir;
+pub mod lattices;